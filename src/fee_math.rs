@@ -0,0 +1,73 @@
+use crate::Error;
+
+// --- SAFE BASIS-POINT MATH ---
+
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+// Which way a fractional result is rounded
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RoundingMode {
+    Down, // Toward negative infinity (truncates positive amounts)
+    Up,   // Toward positive infinity
+}
+
+// amount * bps / 10000 with checked arithmetic; overflow is an error instead of a wrap
+pub fn apply_bps(amount: i128, bps: u32, rounding: RoundingMode) -> Result<i128, Error> {
+    let product = amount
+        .checked_mul(bps as i128)
+        .ok_or(Error::ArithmeticOverflow)?;
+
+    let quotient = product.div_euclid(BPS_DENOMINATOR);
+    match rounding {
+        RoundingMode::Down => Ok(quotient),
+        RoundingMode::Up if product.rem_euclid(BPS_DENOMINATOR) != 0 => {
+            quotient.checked_add(1).ok_or(Error::ArithmeticOverflow)
+        }
+        RoundingMode::Up => Ok(quotient),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_results_ignore_rounding_mode() {
+        assert_eq!(apply_bps(1_000, 100, RoundingMode::Down), Ok(10));
+        assert_eq!(apply_bps(1_000, 100, RoundingMode::Up), Ok(10));
+        assert_eq!(apply_bps(0, 9_999, RoundingMode::Up), Ok(0));
+        assert_eq!(apply_bps(1_000, 0, RoundingMode::Up), Ok(0));
+    }
+
+    #[test]
+    fn fractional_results_follow_rounding_mode() {
+        // 999 * 1% = 9.99
+        assert_eq!(apply_bps(999, 100, RoundingMode::Down), Ok(9));
+        assert_eq!(apply_bps(999, 100, RoundingMode::Up), Ok(10));
+        // Smallest unit at 1 bp
+        assert_eq!(apply_bps(1, 1, RoundingMode::Down), Ok(0));
+        assert_eq!(apply_bps(1, 1, RoundingMode::Up), Ok(1));
+        // Negative amounts round toward the matching infinity
+        assert_eq!(apply_bps(-999, 100, RoundingMode::Down), Ok(-10));
+        assert_eq!(apply_bps(-999, 100, RoundingMode::Up), Ok(-9));
+    }
+
+    #[test]
+    fn u64_amounts_never_overflow() {
+        let max = u64::MAX as i128;
+        assert_eq!(apply_bps(max, 10_000, RoundingMode::Down), Ok(max));
+        assert!(apply_bps(max, u32::MAX, RoundingMode::Up).is_ok());
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        assert_eq!(
+            apply_bps(i128::MAX, 2, RoundingMode::Down),
+            Err(Error::ArithmeticOverflow)
+        );
+        assert_eq!(
+            apply_bps(i128::MIN, 10_000, RoundingMode::Up),
+            Err(Error::ArithmeticOverflow)
+        );
+    }
+}
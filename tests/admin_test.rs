@@ -0,0 +1,353 @@
+#![cfg(test)]
+
+mod common;
+
+use common::Suite;
+use soroban_sdk::{
+    testutils::{Address as _, MockAuth, MockAuthInvoke},
+    Address, BytesN, Env, IntoVal,
+};
+use sorosusu_contracts::{
+    CircleConfig, Error, SoroSusu, SoroSusuClient, MAX_CIRCLES_PER_CREATOR, MAX_PROTOCOL_FEE_BPS,
+};
+
+// Smallest module the host accepts: a wasm header plus the contractenvmetav0
+// custom section declaring interface version 21
+const EMPTY_CONTRACT_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // \0asm, version 1
+    0x00, 0x1e, 0x11, b'c', b'o', b'n', b't', b'r', b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e',
+    b't', b'a', b'v', b'0', // custom section "contractenvmetav0"
+    0x00, 0x00, 0x00, 0x00, // SC_ENV_META_KIND_INTERFACE_VERSION
+    0x00, 0x00, 0x00, 0x15, 0x00, 0x00, 0x00, 0x00, // protocol 21, pre-release 0
+];
+
+#[test]
+fn second_init_cannot_replace_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let attacker = Address::generate(&env);
+
+    client.init(&admin, &0);
+    assert_eq!(
+        client.try_init(&attacker, &10_000),
+        Err(Ok(Error::AlreadyInitialized.into()))
+    );
+
+    // The original admin keeps control
+    client.update_global_fee(&admin, &50);
+}
+
+#[test]
+fn deposit_skims_protocol_fee_to_treasury() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let treasury = Address::generate(&env);
+    s.client.set_protocol_fee(&s.admin, &100, &treasury);
+
+    let circle_id = s.circle();
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+
+    s.client.deposit(&member, &circle_id, &2);
+
+    // 1% of the 2_000 principal goes to the treasury and the member is credited the net
+    assert_eq!(s.balance(&member), 8_000);
+    assert_eq!(s.balance(&treasury), 20);
+    assert_eq!(s.balance(&s.contract_id), 1_980);
+    assert_eq!(
+        s.client.get_member(&circle_id, &member).total_contributed,
+        1_980
+    );
+}
+
+#[test]
+fn init_rejects_fee_above_cap() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, SoroSusu);
+    let client = SoroSusuClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_init(&Address::generate(&env), &(MAX_PROTOCOL_FEE_BPS + 1)),
+        Err(Ok(Error::FeeTooHigh.into()))
+    );
+}
+
+#[test]
+fn protocol_fee_above_cap_rejected() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    assert_eq!(
+        s.client.try_set_protocol_fee(
+            &s.admin,
+            &(MAX_PROTOCOL_FEE_BPS + 1),
+            &Address::generate(&env)
+        ),
+        Err(Ok(Error::FeeTooHigh.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_update_global_fee(&s.admin, &(MAX_PROTOCOL_FEE_BPS + 1)),
+        Err(Ok(Error::FeeTooHigh.into()))
+    );
+}
+
+#[test]
+fn pause_blocks_mutations_until_unpaused() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    let circle_id = s.circle();
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+    let late_joiner = Address::generate(&env);
+
+    s.client.pause(&s.admin);
+
+    assert_eq!(
+        s.try_create_circle_as(&s.creator, &s.config()),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_join_circle(&late_joiner, &circle_id),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_deposit(&member, &circle_id, &1),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_deposit_partial(&member, &circle_id, &500),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_cover_from_reserve(&s.creator, &circle_id, &member),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+
+    // Views keep working while paused
+    assert_eq!(s.client.get_members(&circle_id).len(), 2);
+
+    s.client.unpause(&s.admin);
+
+    s.client.join_circle(&late_joiner, &circle_id);
+    s.client.deposit(&member, &circle_id, &1);
+    assert!(s.client.get_member(&circle_id, &member).has_contributed);
+}
+
+#[test]
+fn pause_blocks_governance_refunds_and_creator_actions() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        bond_amount: 100,
+        ..s.config()
+    });
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+    let new_creator = Address::generate(&env);
+
+    s.client.pause(&s.admin);
+
+    assert_eq!(
+        s.client.try_propose_duration(&member, &circle_id, &1),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_vote_duration(&member, &circle_id, &0, &true),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_propose_cancel(&member, &circle_id),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_vote_cancel(&member, &circle_id, &true),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_claim_refund(&member, &circle_id),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_refund_unformed(&member, &circle_id),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_slash_bond(&s.admin, &circle_id),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_release_bond(&s.admin, &circle_id),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_stake_xlm(&member, &s.token, &100),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_unstake_xlm(&member, &s.token, &100),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_set_contact_hash(&member, &circle_id, &BytesN::from_array(&env, &[1; 32])),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_transfer_creator(&s.creator, &circle_id, &new_creator),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_set_grace_period(&s.creator, &circle_id, &1),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_set_late_fee_cap(&s.creator, &circle_id, &250),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_freeze_circle(&s.creator, &circle_id),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+    assert_eq!(
+        s.client.try_unfreeze_circle(&s.creator, &circle_id),
+        Err(Ok(Error::ContractPaused.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only admin can pause the contract")]
+fn non_admin_cannot_pause() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    s.client.pause(&Address::generate(&env));
+}
+
+#[test]
+fn upgrade_by_admin_succeeds() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    let new_wasm_hash = env.deployer().upload_contract_wasm(EMPTY_CONTRACT_WASM);
+    s.client.upgrade(&new_wasm_hash);
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn upgrade_requires_admin_auth() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let new_wasm_hash = env.deployer().upload_contract_wasm(EMPTY_CONTRACT_WASM);
+
+    let outsider = Address::generate(&env);
+    s.client
+        .mock_auths(&[MockAuth {
+            address: &outsider,
+            invoke: &MockAuthInvoke {
+                contract: &s.contract_id,
+                fn_name: "upgrade",
+                args: (new_wasm_hash.clone(),).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .upgrade(&new_wasm_hash);
+}
+
+#[test]
+fn upgrade_rejects_empty_hash() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    assert_eq!(
+        s.client.try_upgrade(&BytesN::from_array(&env, &[0; 32])),
+        Err(Ok(Error::InvalidUpgradeHash.into()))
+    );
+}
+
+#[test]
+fn whitelist_gates_circle_tokens() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let fake = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let fake_args = CircleConfig {
+        token: fake,
+        ..s.config()
+    };
+
+    // Without a whitelist every token is accepted
+    s.create_circle(&fake_args);
+
+    s.client.set_allowed_token(&s.admin, &s.token, &true);
+    s.circle();
+    assert_eq!(
+        s.try_create_circle_as(&s.creator, &fake_args),
+        Err(Ok(Error::TokenNotAllowed.into()))
+    );
+
+    // Removing the last token keeps the whitelist in force
+    s.client.set_allowed_token(&s.admin, &s.token, &false);
+    assert_eq!(
+        s.try_create_circle_as(&s.creator, &s.config()),
+        Err(Ok(Error::TokenNotAllowed.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Only admin can manage allowed tokens")]
+fn non_admin_cannot_allow_tokens() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    s.client
+        .set_allowed_token(&Address::generate(&env), &s.token, &true);
+}
+
+#[test]
+fn creator_cannot_exceed_circle_limit() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    for _ in 0..MAX_CIRCLES_PER_CREATOR {
+        s.circle();
+    }
+    assert_eq!(
+        s.try_create_circle_as(&s.creator, &s.config()),
+        Err(Ok(Error::TooManyCircles.into()))
+    );
+
+    // The limit is per creator
+    s.create_circle_as(&Address::generate(&env), &s.config());
+}
+
+#[test]
+fn cancelled_circle_frees_a_creator_slot() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    let first = s.circle();
+    for _ in 1..MAX_CIRCLES_PER_CREATOR {
+        s.circle();
+    }
+    let member = s.join(first, 0);
+    s.client.propose_cancel(&member, &first);
+    s.client.vote_cancel(&member, &first, &true);
+
+    s.circle();
+    assert_eq!(
+        s.try_create_circle_as(&s.creator, &s.config()),
+        Err(Ok(Error::TooManyCircles.into()))
+    );
+}
@@ -0,0 +1,261 @@
+#![cfg(test)]
+
+mod common;
+
+use common::{Suite, CYCLE};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+use sorosusu_contracts::{CircleConfig, Error, MAX_CIRCLES_PER_CREATOR};
+
+const MONTH: u64 = 30 * 86_400;
+
+// A weekly circle with three members holding 10_000 each
+fn voting_circle(s: &Suite) -> ([Address; 3], u64) {
+    let circle_id = s.circle();
+    let members = [
+        s.join(circle_id, 10_000),
+        s.join(circle_id, 10_000),
+        s.join(circle_id, 10_000),
+    ];
+
+    (members, circle_id)
+}
+
+#[test]
+fn majority_vote_switches_weekly_circle_to_monthly() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (members, circle_id) = voting_circle(&s);
+
+    let proposal_id = s.client.propose_duration(&members[0], &circle_id, &MONTH);
+    s.client
+        .vote_duration(&members[0], &circle_id, &proposal_id, &true);
+    s.client
+        .vote_duration(&members[1], &circle_id, &proposal_id, &true);
+
+    // Past the old weekly deadline but inside the new monthly one: no penalty
+    env.ledger().with_mut(|li| li.timestamp += CYCLE + 86_400);
+    s.client.deposit(&members[2], &circle_id, &1);
+
+    assert_eq!(s.client.get_group_reserve(&circle_id), 0);
+}
+
+#[test]
+fn minority_vote_keeps_weekly_cadence() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (members, circle_id) = voting_circle(&s);
+
+    let proposal_id = s.client.propose_duration(&members[0], &circle_id, &MONTH);
+    s.client
+        .vote_duration(&members[0], &circle_id, &proposal_id, &true);
+    s.client
+        .vote_duration(&members[1], &circle_id, &proposal_id, &false);
+
+    env.ledger().with_mut(|li| li.timestamp += CYCLE + 86_400);
+    s.client.deposit(&members[2], &circle_id, &1);
+
+    // Still on the weekly deadline, so the 1% late penalty applies
+    assert_eq!(s.client.get_group_reserve(&circle_id), 10);
+}
+
+#[test]
+fn majority_cancel_vote_refunds_principal() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        max_members: 3,
+        ..s.config()
+    });
+    let first = s.join(circle_id, 5_000);
+    let second = s.join(circle_id, 5_000);
+    let third = s.join(circle_id, 5_000);
+    s.client.deposit(&first, &circle_id, &2);
+    s.client.deposit(&second, &circle_id, &1);
+
+    s.client.propose_cancel(&first, &circle_id);
+    s.client.vote_cancel(&first, &circle_id, &true);
+    s.client.vote_cancel(&third, &circle_id, &false);
+    // One of three is not yet a majority
+    s.client.deposit(&third, &circle_id, &1);
+
+    s.client.vote_cancel(&second, &circle_id, &true);
+    assert_eq!(
+        s.client.try_deposit(&third, &circle_id, &1),
+        Err(Ok(Error::CircleCancelled.into()))
+    );
+
+    assert_eq!(s.client.claim_refund(&first, &circle_id), 2_000);
+    assert_eq!(s.client.claim_refund(&second, &circle_id), 1_000);
+    assert_eq!(s.client.claim_refund(&third, &circle_id), 1_000);
+    for member in [&first, &second, &third] {
+        assert_eq!(s.balance(member), 5_000);
+    }
+    assert_eq!(s.balance(&s.contract_id), 0);
+}
+
+#[test]
+#[should_panic(expected = "Circle has not been cancelled")]
+fn refund_requires_cancelled_circle() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        max_members: 3,
+        ..s.config()
+    });
+    let member = s.join(circle_id, 5_000);
+    s.fill(circle_id, 1);
+    s.client.deposit(&member, &circle_id, &1);
+
+    s.client.claim_refund(&member, &circle_id);
+}
+
+// A circle with a 500 bond needing three members that only two have joined.
+// Deposits never open, so the only member funds held are `member`'s 10 prepaid penalty.
+fn unformed_circle(s: &Suite) -> (Address, u64) {
+    let circle_id = s.create_circle(&CircleConfig {
+        min_members: 3,
+        bond_amount: 500,
+        ..s.config()
+    });
+    let member = s.join(circle_id, 1_000);
+    s.fill(circle_id, 1);
+    s.client.prepay_penalty(&member, &circle_id, &10);
+
+    (member, circle_id)
+}
+
+#[test]
+fn unformed_circle_returns_bond_and_prepaid_credit_after_formation_deadline() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = unformed_circle(&s);
+    assert_eq!(
+        s.client.try_deposit(&member, &circle_id, &1),
+        Err(Ok(Error::NotEnoughMembers.into()))
+    );
+    assert_eq!(s.balance(&s.creator), 0);
+
+    env.ledger().with_mut(|li| li.timestamp += CYCLE + 1);
+    assert_eq!(s.client.refund_unformed(&member, &circle_id), 10);
+
+    assert_eq!(s.balance(&member), 1_000);
+    assert_eq!(s.balance(&s.creator), 500);
+    assert_eq!(s.balance(&s.contract_id), 0);
+    assert_eq!(s.client.get_group_reserve(&circle_id), 0);
+    assert_eq!(
+        s.client
+            .try_join_circle(&Address::generate(&env), &circle_id),
+        Err(Ok(Error::CircleCancelled.into()))
+    );
+}
+
+#[test]
+fn unformed_circle_cancels_even_with_nothing_to_refund() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        min_members: 3,
+        ..s.config()
+    });
+    let member = s.join(circle_id, 0);
+    s.fill(circle_id, 1);
+
+    env.ledger().with_mut(|li| li.timestamp += CYCLE + 1);
+    assert_eq!(
+        s.client
+            .try_join_circle(&Address::generate(&env), &circle_id),
+        Err(Ok(Error::FormationDeadlinePassed.into()))
+    );
+
+    assert_eq!(s.client.refund_unformed(&member, &circle_id), 0);
+    assert_eq!(
+        s.client
+            .try_join_circle(&Address::generate(&env), &circle_id),
+        Err(Ok(Error::CircleCancelled.into()))
+    );
+}
+
+#[test]
+fn open_cancel_vote_cannot_end_an_unformed_circle_twice() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = unformed_circle(&s);
+    let second = s.client.get_members(&circle_id).get(1).unwrap().address;
+
+    // The vote opens just before the formation deadline and is still running after it
+    env.ledger().with_mut(|li| li.timestamp += CYCLE - 100);
+    s.client.propose_cancel(&member, &circle_id);
+    s.client.vote_cancel(&member, &circle_id, &true);
+    env.ledger().with_mut(|li| li.timestamp += 101);
+    s.client.refund_unformed(&member, &circle_id);
+
+    assert_eq!(
+        s.client.try_vote_cancel(&second, &circle_id, &true),
+        Err(Ok(Error::CircleCancelled.into()))
+    );
+    // Ending the circle freed exactly one of the creator's slots
+    for _ in 0..MAX_CIRCLES_PER_CREATOR {
+        s.circle();
+    }
+    assert_eq!(
+        s.try_create_circle_as(&s.creator, &s.config()),
+        Err(Ok(Error::TooManyCircles.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Formation deadline has not passed")]
+fn refund_before_formation_deadline_rejected() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = unformed_circle(&s);
+
+    s.client.refund_unformed(&member, &circle_id);
+}
+
+// A bonded 100 circle with one funded member and one filler seat
+fn freezable_circle(s: &Suite) -> (Address, u64) {
+    let circle_id = s.create_circle(&CircleConfig {
+        contribution_amount: 100,
+        bond_amount: 100,
+        ..s.config()
+    });
+    let member = s.join(circle_id, 1_000);
+    s.fill(circle_id, 1);
+
+    (member, circle_id)
+}
+
+#[test]
+fn deposit_fails_while_frozen_and_succeeds_after_unfreeze() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = freezable_circle(&s);
+
+    s.client.freeze_circle(&s.creator, &circle_id);
+    assert_eq!(
+        s.client.try_deposit(&member, &circle_id, &1),
+        Err(Ok(Error::CircleFrozen.into()))
+    );
+    assert_eq!(
+        s.client
+            .try_join_circle(&Address::generate(&env), &circle_id),
+        Err(Ok(Error::CircleFrozen.into()))
+    );
+
+    s.client.unfreeze_circle(&s.creator, &circle_id);
+    s.client.deposit(&member, &circle_id, &1);
+}
+
+#[test]
+#[should_panic(expected = "Only creator can freeze or unfreeze circle")]
+fn non_creator_cannot_freeze() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = freezable_circle(&s);
+
+    s.client.freeze_circle(&member, &circle_id);
+}
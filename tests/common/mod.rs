@@ -0,0 +1,114 @@
+// Shared fixture for the SoroSusu integration tests
+#![allow(dead_code)]
+
+use soroban_sdk::{symbol_short, testutils::Address as _, token, Address, Env};
+use sorosusu_contracts::{CircleConfig, PenaltyDestination, SoroSusu, SoroSusuClient};
+
+pub const CYCLE: u64 = 604_800; // One week
+
+// A freshly initialised contract with auths mocked and one Stellar asset to save in
+pub struct Suite<'a> {
+    pub env: &'a Env,
+    pub client: SoroSusuClient<'a>,
+    pub contract_id: Address,
+    pub admin: Address,
+    pub creator: Address,
+    pub token: Address,
+    pub token_admin: token::StellarAssetClient<'a>,
+    pub token_client: token::Client<'a>,
+}
+
+impl<'a> Suite<'a> {
+    pub fn new(env: &'a Env) -> Self {
+        Self::with_fee(env, 0)
+    }
+
+    pub fn with_fee(env: &'a Env, global_fee: u32) -> Self {
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, SoroSusu);
+        let client = SoroSusuClient::new(env, &contract_id);
+        let admin = Address::generate(env);
+        let token = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        client.init(&admin, &global_fee);
+
+        Suite {
+            env,
+            client,
+            contract_id,
+            admin,
+            creator: Address::generate(env),
+            token_admin: token::StellarAssetClient::new(env, &token),
+            token_client: token::Client::new(env, &token),
+            token,
+        }
+    }
+
+    // 1_000 per weekly cycle, five seats, default minimum, no bond, penalties to the reserve
+    pub fn config(&self) -> CircleConfig {
+        CircleConfig {
+            contribution_amount: 1_000,
+            max_members: 5,
+            min_members: 0,
+            token: self.token.clone(),
+            cycle_duration: CYCLE,
+            grace_period: 0,
+            bond_amount: 0,
+            penalty_destination: PenaltyDestination::Reserve,
+            name: symbol_short!(""),
+        }
+    }
+
+    pub fn circle(&self) -> u64 {
+        self.create_circle(&self.config())
+    }
+
+    pub fn create_circle(&self, config: &CircleConfig) -> u64 {
+        self.create_circle_as(&self.creator, config)
+    }
+
+    pub fn create_circle_as(&self, creator: &Address, config: &CircleConfig) -> u64 {
+        self.fund_bond(creator, config);
+        self.client.create_circle(creator, config)
+    }
+
+    pub fn try_create_circle_as(
+        &self,
+        creator: &Address,
+        config: &CircleConfig,
+    ) -> Result<Result<u64, soroban_sdk::Error>, Result<soroban_sdk::Error, soroban_sdk::InvokeError>>
+    {
+        self.fund_bond(creator, config);
+        self.client.try_create_circle(creator, config)
+    }
+
+    // A new member holding `funds` of the suite token, joined to the circle
+    pub fn join(&self, circle_id: u64, funds: i128) -> Address {
+        let member = Address::generate(self.env);
+        if funds > 0 {
+            self.token_admin.mint(&member, &funds);
+        }
+        self.client.join_circle(&member, &circle_id);
+        member
+    }
+
+    // Seats `count` unfunded members, e.g. to meet min_members
+    pub fn fill(&self, circle_id: u64, count: u32) {
+        for _ in 0..count {
+            self.join(circle_id, 0);
+        }
+    }
+
+    pub fn balance(&self, owner: &Address) -> i128 {
+        self.token_client.balance(owner)
+    }
+
+    fn fund_bond(&self, creator: &Address, config: &CircleConfig) {
+        if config.bond_amount > 0 {
+            token::StellarAssetClient::new(self.env, &config.token)
+                .mint(creator, &(config.bond_amount as i128));
+        }
+    }
+}
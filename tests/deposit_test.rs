@@ -0,0 +1,289 @@
+#![cfg(test)]
+
+mod common;
+
+use common::{Suite, CYCLE};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+use sorosusu_contracts::{CircleConfig, Error, MAX_PROTOCOL_FEE_BPS};
+
+// A bonded 1_000 circle with one funded member and one filler seat
+fn installment_circle(s: &Suite) -> (Address, u64) {
+    let circle_id = s.create_circle(&CircleConfig {
+        bond_amount: 100,
+        ..s.config()
+    });
+    let member = s.join(circle_id, 1_000);
+    s.fill(circle_id, 1);
+
+    (member, circle_id)
+}
+
+#[test]
+fn installments_summing_to_contribution_complete_it() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = installment_circle(&s);
+
+    s.client.deposit_partial(&member, &circle_id, &400);
+    let after_first = s.client.get_member(&circle_id, &member);
+    assert!(!after_first.has_contributed);
+    assert_eq!(after_first.paid_this_cycle, 400);
+
+    s.client.deposit_partial(&member, &circle_id, &600);
+    let after_second = s.client.get_member(&circle_id, &member);
+    assert!(after_second.has_contributed);
+    assert_eq!(after_second.contribution_count, 1);
+    assert_eq!(after_second.paid_this_cycle, 0);
+}
+
+#[test]
+fn short_installment_does_not_complete_contribution() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = installment_circle(&s);
+
+    s.client.deposit_partial(&member, &circle_id, &999);
+
+    let member_info = s.client.get_member(&circle_id, &member);
+    assert!(!member_info.has_contributed);
+    assert_eq!(member_info.contribution_count, 0);
+}
+
+#[test]
+fn installment_beyond_contribution_rejected() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = installment_circle(&s);
+
+    s.client.deposit_partial(&member, &circle_id, &700);
+    assert_eq!(
+        s.client.try_deposit_partial(&member, &circle_id, &301),
+        Err(Ok(Error::ContributionExceeded.into()))
+    );
+}
+
+#[test]
+fn full_deposit_after_installment_charges_only_the_rest() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = installment_circle(&s);
+
+    s.client.deposit_partial(&member, &circle_id, &400);
+    s.client.deposit(&member, &circle_id, &1);
+
+    let member_info = s.client.get_member(&circle_id, &member);
+    assert_eq!(s.balance(&member), 0);
+    assert_eq!(member_info.paid_this_cycle, 0);
+    assert_eq!(member_info.total_contributed, 1_000);
+    assert_eq!(member_info.contribution_count, 1);
+}
+
+#[test]
+fn total_contributed_sums_all_deposits() {
+    let env = Env::default();
+    // A 1% platform fee is charged but is not principal
+    let s = Suite::with_fee(&env, 100);
+    let circle_id = s.circle();
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+
+    s.client.deposit(&member, &circle_id, &2);
+    s.client.deposit_partial(&member, &circle_id, &400);
+
+    assert_eq!(
+        s.client.get_member(&circle_id, &member).total_contributed,
+        2_400
+    );
+}
+
+// A circle for `contribution` with one member holding u64::MAX and one filler seat
+fn large_circle(s: &Suite, contribution: u64) -> (Address, u64) {
+    let circle_id = s.create_circle(&CircleConfig {
+        contribution_amount: contribution,
+        ..s.config()
+    });
+    let member = s.join(circle_id, u64::MAX as i128);
+    s.fill(circle_id, 1);
+
+    (member, circle_id)
+}
+
+#[test]
+fn fee_on_near_max_contribution_does_not_wrap() {
+    let env = Env::default();
+    let s = Suite::with_fee(&env, MAX_PROTOCOL_FEE_BPS);
+    let contribution = u64::MAX / 2;
+    let (member, circle_id) = large_circle(&s, contribution);
+
+    s.client.deposit(&member, &circle_id, &1);
+
+    // A 10% fee on top, with no wrap-around in the bps multiply
+    let paid = u64::MAX as i128 - s.balance(&member);
+    assert_eq!(paid, (contribution + contribution / 10) as i128);
+}
+
+#[test]
+fn deposit_total_beyond_u64_is_rejected() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = large_circle(&s, u64::MAX - 1);
+
+    assert_eq!(
+        s.client.try_deposit(&member, &circle_id, &2),
+        Err(Ok(Error::ArithmeticOverflow.into()))
+    );
+}
+
+// A `contribution` circle paid 100 cycles late, where the late fee reaches its 100% cap
+fn full_penalty_circle(s: &Suite, contribution: u64) -> (Address, u64) {
+    let (member, circle_id) = large_circle(s, contribution);
+    s.client.set_late_fee_cap(&s.creator, &circle_id, &10_000);
+    s.env
+        .ledger()
+        .with_mut(|li| li.timestamp += 100 * CYCLE + 1);
+
+    (member, circle_id)
+}
+
+#[test]
+fn full_late_penalty_on_near_max_contribution_does_not_wrap() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let contribution = u64::MAX / 2;
+    let (member, circle_id) = full_penalty_circle(&s, contribution);
+
+    s.client.deposit(&member, &circle_id, &1);
+
+    let paid = u64::MAX as i128 - s.balance(&member);
+    assert_eq!(paid, 2 * contribution as i128);
+    assert_eq!(s.client.get_group_reserve(&circle_id), contribution);
+}
+
+#[test]
+fn full_late_penalty_beyond_u64_is_rejected() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = full_penalty_circle(&s, u64::MAX / 2 + 1);
+
+    assert_eq!(
+        s.client.try_deposit(&member, &circle_id, &1),
+        Err(Ok(Error::ArithmeticOverflow.into()))
+    );
+}
+
+#[test]
+fn deposit_above_i64_max_transfers_exact_amount() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let contribution = i64::MAX as u64 + 1_000;
+    let (member, circle_id) = large_circle(&s, contribution);
+
+    s.client.deposit(&member, &circle_id, &1);
+
+    // Widened to i128 without wrapping negative
+    assert_eq!(s.balance(&s.contract_id), contribution as i128);
+    assert_eq!(s.balance(&member), (u64::MAX - contribution) as i128);
+}
+
+#[test]
+fn total_contributed_beyond_u64_is_rejected() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let contribution = i64::MAX as u64 + 1_000;
+    let (member, circle_id) = large_circle(&s, contribution);
+    s.token_admin.mint(&member, &(contribution as i128));
+
+    s.client.deposit(&member, &circle_id, &1);
+    assert_eq!(
+        s.client.try_deposit(&member, &circle_id, &1),
+        Err(Ok(Error::ArithmeticOverflow.into()))
+    );
+    assert_eq!(
+        s.client.get_member(&circle_id, &member).total_contributed,
+        contribution
+    );
+}
+
+#[test]
+fn token_volume_is_tracked_per_token() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let member = Address::generate(&env);
+    let xlm = env
+        .register_stellar_asset_contract_v2(s.admin.clone())
+        .address();
+    s.token_admin.mint(&member, &10_000);
+    token::StellarAssetClient::new(&env, &xlm).mint(&member, &10_000);
+
+    let mut circles = [0u64; 2];
+    for (circle, (token, amount)) in circles.iter_mut().zip([(&s.token, 1_000), (&xlm, 300)]) {
+        *circle = s.create_circle(&CircleConfig {
+            contribution_amount: amount,
+            token: token.clone(),
+            ..s.config()
+        });
+        s.client.join_circle(&member, circle);
+        s.fill(*circle, 1);
+    }
+
+    s.client.deposit(&member, &circles[0], &2);
+    s.client.deposit_partial(&member, &circles[0], &400);
+    s.client.deposit(&member, &circles[1], &1);
+    s.client.prepay_penalty(&member, &circles[1], &10);
+
+    assert_eq!(s.client.get_token_volume(&s.token), 2_400);
+    assert_eq!(s.client.get_token_volume(&xlm), 310);
+    assert_eq!(s.client.get_token_volume(&Address::generate(&env)), 0);
+}
+
+#[test]
+fn next_due_counts_down_to_the_penalty_deadline() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        grace_period: 100,
+        ..s.config()
+    });
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+
+    // Installments alone don't move the deadline
+    s.client.deposit_partial(&member, &circle_id, &400);
+    assert_eq!(s.client.next_due(&circle_id, &member), CYCLE + 100);
+
+    env.ledger().with_mut(|li| li.timestamp += CYCLE + 100);
+    assert_eq!(s.client.next_due(&circle_id, &member), 0);
+    assert!(!s.client.is_overdue(&circle_id, &member));
+
+    // Overdue exactly when the completing installment draws the late penalty
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    assert!(s.client.is_overdue(&circle_id, &member));
+    s.client.deposit_partial(&member, &circle_id, &600);
+    assert_eq!(s.balance(&member), 10_000 - 1_000 - 10);
+}
+
+#[test]
+fn paid_and_covered_members_are_due_at_the_following_deadline() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        bond_amount: 1_000,
+        ..s.config()
+    });
+    let payer = s.join(circle_id, 10_000);
+    let covered = s.join(circle_id, 0);
+    s.client.slash_bond(&s.admin, &circle_id);
+
+    env.ledger().with_mut(|li| li.timestamp += 10);
+    s.client
+        .cover_from_reserve(&s.creator, &circle_id, &covered);
+    assert_eq!(s.client.next_due(&circle_id, &covered), 2 * CYCLE - 10);
+    assert_eq!(s.client.next_due(&circle_id, &payer), CYCLE - 10);
+
+    s.client.deposit(&payer, &circle_id, &1);
+    assert_eq!(s.client.next_due(&circle_id, &payer), 2 * CYCLE - 10);
+    assert!(!s.client.is_overdue(&circle_id, &payer));
+}
@@ -0,0 +1,110 @@
+#![cfg(test)]
+
+mod common;
+
+use common::{Suite, CYCLE};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, IntoVal, TryFromVal, Val, Vec,
+};
+use sorosusu_contracts::{CircleConfig, CircleFullEvent, DepositEvent, MemberJoinedEvent};
+
+// A bonded three-seat circle
+fn setup(s: &Suite) -> u64 {
+    s.create_circle(&CircleConfig {
+        max_members: 3,
+        bond_amount: 100,
+        ..s.config()
+    })
+}
+
+fn last_event(env: &Env) -> (Vec<Val>, Val) {
+    let (_, topics, data) = env.events().all().last().unwrap();
+    (topics, data)
+}
+
+#[test]
+fn join_circle_emits_member_joined_event() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = setup(&s);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    s.client.join_circle(&first, &circle_id);
+    s.client.join_circle(&second, &circle_id);
+
+    let (topics, data) = last_event(&env);
+    assert_eq!(topics, (symbol_short!("joined"), circle_id).into_val(&env));
+    assert_eq!(
+        MemberJoinedEvent::try_from_val(&env, &data).unwrap(),
+        MemberJoinedEvent {
+            circle_id,
+            member: second,
+            index: 1
+        }
+    );
+}
+
+#[test]
+fn deposit_emits_deposit_event() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = setup(&s);
+
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+    s.client.deposit(&member, &circle_id, &1);
+
+    let (topics, data) = last_event(&env);
+    assert_eq!(topics, (symbol_short!("deposit"), circle_id).into_val(&env));
+    assert_eq!(
+        DepositEvent::try_from_val(&env, &data).unwrap(),
+        DepositEvent {
+            circle_id,
+            member,
+            amount: 1_000,
+            late: false
+        }
+    );
+}
+
+#[test]
+fn late_deposit_event_is_flagged_late() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = setup(&s);
+
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&member, &circle_id, &1);
+
+    let (_, data) = last_event(&env);
+    let event = DepositEvent::try_from_val(&env, &data).unwrap();
+    assert!(event.late);
+    assert_eq!(event.amount, 1_010); // 1% late penalty on top
+}
+
+#[test]
+fn final_join_emits_circle_full_event() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = setup(&s);
+
+    s.client.join_circle(&Address::generate(&env), &circle_id);
+    s.client.join_circle(&Address::generate(&env), &circle_id);
+    assert!(!s.client.get_is_full(&circle_id));
+
+    s.client.join_circle(&Address::generate(&env), &circle_id);
+    assert!(s.client.get_is_full(&circle_id));
+
+    let (topics, data) = last_event(&env);
+    assert_eq!(topics, (symbol_short!("full"), circle_id).into_val(&env));
+    assert_eq!(
+        CircleFullEvent::try_from_val(&env, &data).unwrap(),
+        CircleFullEvent { circle_id }
+    );
+}
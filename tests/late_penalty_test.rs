@@ -0,0 +1,166 @@
+#![cfg(test)]
+
+mod common;
+
+use common::{Suite, CYCLE};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+use sorosusu_contracts::{CircleConfig, PenaltyDestination};
+
+const GRACE: u64 = 86_400;
+
+// A 1_000 circle with one member holding 10_000 and one filler seat; the late penalty is 10
+fn late_circle(s: &Suite, destination: PenaltyDestination) -> (Address, u64) {
+    let circle_id = s.create_circle(&CircleConfig {
+        penalty_destination: destination,
+        ..s.config()
+    });
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+
+    (member, circle_id)
+}
+
+#[test]
+fn reserve_destination_credits_group_reserve() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = late_circle(&s, PenaltyDestination::Reserve);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&member, &circle_id, &1);
+
+    assert_eq!(s.client.get_group_reserve(&circle_id), 10);
+    assert_eq!(s.balance(&s.contract_id), 1_010);
+}
+
+#[test]
+fn treasury_destination_transfers_penalty_out() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let treasury = Address::generate(&env);
+    let (member, circle_id) = late_circle(&s, PenaltyDestination::Treasury(treasury.clone()));
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&member, &circle_id, &1);
+
+    assert_eq!(s.client.get_group_reserve(&circle_id), 0);
+    assert_eq!(s.balance(&treasury), 10);
+    assert_eq!(s.balance(&s.contract_id), 1_000);
+}
+
+#[test]
+fn burn_destination_destroys_penalty() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = late_circle(&s, PenaltyDestination::Burn);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&member, &circle_id, &1);
+
+    assert_eq!(s.client.get_group_reserve(&circle_id), 0);
+    assert_eq!(s.balance(&s.contract_id), 1_000);
+}
+
+#[test]
+fn prepaid_penalty_covers_later_late_deposit() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = late_circle(&s, PenaltyDestination::Reserve);
+
+    // The credit stays the member's until a late deposit uses it
+    s.client.prepay_penalty(&member, &circle_id, &10);
+    assert_eq!(s.client.get_member(&circle_id, &member).penalty_credit, 10);
+    assert_eq!(s.client.get_group_reserve(&circle_id), 0);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&member, &circle_id, &1);
+
+    assert_eq!(s.balance(&member), 10_000 - 10 - 1_000);
+    assert_eq!(s.client.get_member(&circle_id, &member).penalty_credit, 0);
+    assert_eq!(s.client.get_group_reserve(&circle_id), 10);
+}
+
+// A circle created with a one-day grace period
+fn grace_circle(s: &Suite) -> (Address, u64) {
+    let circle_id = s.create_circle(&CircleConfig {
+        grace_period: GRACE,
+        ..s.config()
+    });
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+
+    (member, circle_id)
+}
+
+#[test]
+fn deposit_inside_grace_window_has_no_penalty() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = grace_circle(&s);
+
+    env.ledger().with_mut(|li| li.timestamp += CYCLE + GRACE);
+    s.client.deposit(&member, &circle_id, &1);
+
+    assert_eq!(s.balance(&member), 9_000);
+    assert_eq!(s.client.get_group_reserve(&circle_id), 0);
+}
+
+#[test]
+fn deposit_past_grace_window_is_penalized() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = grace_circle(&s);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += CYCLE + GRACE + 1);
+    s.client.deposit(&member, &circle_id, &1);
+
+    assert_eq!(s.balance(&member), 8_990);
+    assert_eq!(s.client.get_group_reserve(&circle_id), 10);
+}
+
+#[test]
+fn creator_can_extend_grace_period_later() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = late_circle(&s, PenaltyDestination::Reserve);
+    s.client.set_grace_period(&s.creator, &circle_id, &GRACE);
+
+    env.ledger().with_mut(|li| li.timestamp += CYCLE + GRACE);
+    s.client.deposit(&member, &circle_id, &1);
+
+    assert_eq!(s.balance(&member), 9_000);
+}
+
+// Deposits one round `elapsed` seconds after creation with late fees capped at 2.5%
+// and returns what the member paid
+fn paid_after(elapsed: u64) -> i128 {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let (member, circle_id) = late_circle(&s, PenaltyDestination::Reserve);
+    s.client.set_late_fee_cap(&s.creator, &circle_id, &250);
+
+    env.ledger().with_mut(|li| li.timestamp += elapsed);
+    s.client.deposit(&member, &circle_id, &1);
+
+    10_000 - s.balance(&member)
+}
+
+#[test]
+fn on_time_deposit_has_no_late_fee() {
+    assert_eq!(paid_after(CYCLE), 1_000);
+}
+
+#[test]
+fn first_period_late_pays_base_fee() {
+    assert_eq!(paid_after(CYCLE + 3_600), 1_010);
+}
+
+#[test]
+fn three_periods_late_is_capped() {
+    // 1% * (1 + 3) = 4%, capped at 2.5%
+    assert_eq!(paid_after(4 * CYCLE + 1), 1_025);
+}
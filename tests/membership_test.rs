@@ -0,0 +1,363 @@
+#![cfg(test)]
+
+mod common;
+
+use common::{Suite, CYCLE};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    vec, Address, BytesN, Env, IntoVal,
+};
+use sorosusu_contracts::{CircleConfig, Error, MAX_CIRCLES_PER_CREATOR};
+
+#[test]
+fn circles_of_member_lists_every_joined_circle() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let args = CircleConfig {
+        contribution_amount: 100,
+        bond_amount: 100,
+        ..s.config()
+    };
+    let user = Address::generate(&env);
+
+    let first = s.create_circle(&args);
+    let second = s.create_circle(&args);
+    let third = s.create_circle(&args);
+    let _unjoined = s.create_circle(&args);
+
+    assert_eq!(s.client.circles_of_member(&user), vec![&env]);
+
+    s.client.join_circle(&user, &first);
+    s.client.join_circle(&user, &second);
+    s.client.join_circle(&user, &third);
+
+    assert_eq!(
+        s.client.circles_of_member(&user),
+        vec![&env, first, second, third]
+    );
+}
+
+#[test]
+fn circles_of_member_drops_cancelled_circles() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let kept = s.circle();
+    let cancelled = s.circle();
+    let user = Address::generate(&env);
+    let other = s.join(cancelled, 0);
+    s.client.join_circle(&user, &kept);
+    s.client.join_circle(&user, &cancelled);
+
+    s.client.propose_cancel(&user, &cancelled);
+    s.client.vote_cancel(&user, &cancelled, &true);
+    s.client.vote_cancel(&other, &cancelled, &true);
+
+    assert_eq!(s.client.circles_of_member(&user), vec![&env, kept]);
+}
+
+#[test]
+fn deposits_blocked_until_min_members_join() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    // 0 selects the default minimum of two members
+    let circle_id = s.create_circle(&CircleConfig {
+        contribution_amount: 100,
+        bond_amount: 100,
+        ..s.config()
+    });
+    let first = s.join(circle_id, 1_000);
+
+    assert_eq!(
+        s.client.try_deposit(&first, &circle_id, &1),
+        Err(Ok(Error::NotEnoughMembers.into()))
+    );
+
+    s.fill(circle_id, 1);
+    s.client.deposit(&first, &circle_id, &1);
+
+    assert_eq!(s.balance(&first), 900);
+}
+
+#[test]
+#[should_panic(expected = "Minimum members exceeds maximum members")]
+fn min_members_above_max_members_rejected() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    s.create_circle(&CircleConfig {
+        contribution_amount: 100,
+        max_members: 3,
+        min_members: 4,
+        bond_amount: 100,
+        ..s.config()
+    });
+}
+
+#[test]
+fn get_members_returns_roster_in_join_order() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+
+    let first = s.join(circle_id, 0);
+    let second = s.join(circle_id, 1_000);
+    let third = s.join(circle_id, 0);
+    s.client.deposit(&second, &circle_id, &1);
+
+    let members = s.client.get_members(&circle_id);
+    assert_eq!(members.len(), 3);
+    assert_eq!(members.get(0).unwrap().address, first);
+    assert_eq!(members.get(1).unwrap().address, second);
+    assert_eq!(members.get(2).unwrap().address, third);
+    assert!(!members.get(0).unwrap().has_contributed);
+    assert!(members.get(1).unwrap().has_contributed);
+    assert!(!members.get(2).unwrap().has_contributed);
+}
+
+#[test]
+fn contribution_views_split_paid_and_delinquent_members() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+
+    let first = s.join(circle_id, 1_000);
+    let second = s.join(circle_id, 0);
+    let third = s.join(circle_id, 1_000);
+    for member in [&first, &third] {
+        s.client.deposit(member, &circle_id, &1);
+    }
+
+    assert_eq!(
+        s.client.who_has_contributed(&circle_id),
+        vec![&env, first, third]
+    );
+    assert_eq!(s.client.who_is_delinquent(&circle_id), vec![&env, second]);
+}
+
+#[test]
+fn member_who_stops_paying_turns_delinquent_next_cycle() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        max_members: 2,
+        ..s.config()
+    });
+    let steady = s.join(circle_id, 10_000);
+    let lapsed = s.join(circle_id, 10_000);
+    for member in [&steady, &lapsed] {
+        s.client.deposit(member, &circle_id, &1);
+    }
+    assert_eq!(
+        s.client.who_has_contributed(&circle_id),
+        vec![&env, steady.clone(), lapsed.clone()]
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += CYCLE + 1);
+    s.client.deposit(&steady, &circle_id, &1);
+
+    assert_eq!(s.client.who_has_contributed(&circle_id), vec![&env, steady]);
+    assert_eq!(
+        s.client.who_is_delinquent(&circle_id),
+        vec![&env, lapsed.clone()]
+    );
+    assert!(s.client.get_member(&circle_id, &lapsed).has_contributed);
+}
+
+#[test]
+fn contact_hash_round_trips_through_get_member() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+    let member = s.join(circle_id, 0);
+
+    assert_eq!(
+        s.client.get_member(&circle_id, &member).contact_hash,
+        BytesN::from_array(&env, &[0; 32])
+    );
+
+    let first = BytesN::from_array(&env, &[7; 32]);
+    s.client.set_contact_hash(&member, &circle_id, &first);
+    assert_eq!(s.client.get_member(&circle_id, &member).contact_hash, first);
+
+    let updated = BytesN::from_array(&env, &[9; 32]);
+    s.client.set_contact_hash(&member, &circle_id, &updated);
+    assert_eq!(
+        s.client.get_member(&circle_id, &member).contact_hash,
+        updated
+    );
+}
+
+#[test]
+#[should_panic(expected = "User is not a member of this circle")]
+fn non_member_cannot_set_contact_hash() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+
+    s.client.set_contact_hash(
+        &Address::generate(&env),
+        &circle_id,
+        &BytesN::from_array(&env, &[1; 32]),
+    );
+}
+
+#[test]
+fn new_creator_can_administer_circle() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+    let new_creator = Address::generate(&env);
+
+    s.client
+        .transfer_creator(&s.creator, &circle_id, &new_creator);
+
+    s.client.freeze_circle(&new_creator, &circle_id);
+    s.client.unfreeze_circle(&new_creator, &circle_id);
+}
+
+#[test]
+#[should_panic(expected = "Only creator can freeze or unfreeze circle")]
+fn old_creator_loses_control_after_transfer() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+
+    s.client
+        .transfer_creator(&s.creator, &circle_id, &Address::generate(&env));
+    s.client.freeze_circle(&s.creator, &circle_id);
+}
+
+#[test]
+#[should_panic(expected = "Only creator can transfer the circle")]
+fn non_creator_cannot_transfer() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+    let outsider = Address::generate(&env);
+
+    s.client
+        .transfer_creator(&outsider, &circle_id, &Address::generate(&env));
+}
+
+#[test]
+fn new_creator_repays_bond_and_takes_the_circle_slot() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let bonded = s.create_circle(&CircleConfig {
+        bond_amount: 500,
+        ..s.config()
+    });
+    for _ in 1..MAX_CIRCLES_PER_CREATOR {
+        s.circle();
+    }
+    let new_creator = Address::generate(&env);
+    s.token_admin.mint(&new_creator, &500);
+
+    s.client.transfer_creator(&s.creator, &bonded, &new_creator);
+
+    assert_eq!(s.balance(&s.creator), 500);
+    assert_eq!(s.balance(&new_creator), 0);
+    assert_eq!(s.balance(&s.contract_id), 500);
+    // The outgoing creator is back under the cap
+    s.circle();
+}
+
+#[test]
+fn transfer_to_creator_at_cap_rejected() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+    let busy = Address::generate(&env);
+    for _ in 0..MAX_CIRCLES_PER_CREATOR {
+        s.create_circle_as(&busy, &s.config());
+    }
+
+    assert_eq!(
+        s.client.try_transfer_creator(&s.creator, &circle_id, &busy),
+        Err(Ok(Error::TooManyCircles.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
+fn transfer_requires_new_creator_auth() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+    let new_creator = Address::generate(&env);
+
+    s.client
+        .mock_auths(&[MockAuth {
+            address: &s.creator,
+            invoke: &MockAuthInvoke {
+                contract: &s.contract_id,
+                fn_name: "transfer_creator",
+                args: (s.creator.clone(), circle_id, new_creator.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .transfer_creator(&s.creator, &circle_id, &new_creator);
+}
+
+#[test]
+fn named_and_unnamed_circles_report_their_names() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+
+    let named = s.create_circle(&CircleConfig {
+        name: symbol_short!("market"),
+        ..s.config()
+    });
+    let unnamed = s.circle();
+
+    assert_eq!(s.client.get_circle_name(&named), symbol_short!("market"));
+    assert_eq!(s.client.get_circle_name(&unnamed), symbol_short!(""));
+}
+
+#[test]
+fn display_info_reports_cached_token_decimals() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    // Stellar asset contracts always report 7 decimals
+    assert_eq!(s.token_client.decimals(), 7);
+
+    let circle_id = s.create_circle(&CircleConfig {
+        contribution_amount: 25_000_000,
+        ..s.config()
+    });
+
+    assert_eq!(
+        s.client.get_display_info(&circle_id),
+        (s.token.clone(), 7, 25_000_000)
+    );
+}
+
+// Just enough of a token for create_circle: a bond transfer and a decimals query
+#[contract]
+struct SixDecimalToken;
+
+#[contractimpl]
+impl SixDecimalToken {
+    pub fn decimals(_env: Env) -> u32 {
+        6
+    }
+
+    pub fn transfer(_env: Env, _from: Address, _to: Address, _amount: i128) {}
+}
+
+#[test]
+fn get_decimals_reports_non_standard_token_decimals() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let token = env.register_contract(None, SixDecimalToken);
+
+    let circle_id = s.create_circle(&CircleConfig {
+        contribution_amount: 2_500_000,
+        token: token.clone(),
+        ..s.config()
+    });
+
+    assert_eq!(s.client.get_decimals(&circle_id), 6);
+    assert_eq!(s.client.get_display_info(&circle_id), (token, 6, 2_500_000));
+}
@@ -0,0 +1,241 @@
+#![cfg(test)]
+
+mod common;
+
+use common::{Suite, CYCLE};
+use soroban_sdk::{testutils::Ledger, Env};
+use sorosusu_contracts::{CircleConfig, Error};
+
+#[test]
+fn late_deposit_grows_group_reserve() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let bonded = CircleConfig {
+        bond_amount: 100,
+        ..s.config()
+    };
+    let circle_id = s.create_circle(&bonded);
+    let other_circle = s.create_circle(&bonded);
+    let member = s.join(circle_id, 10_000);
+    s.fill(circle_id, 1);
+
+    assert_eq!(s.client.get_group_reserve(&circle_id), 0);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&member, &circle_id, &1);
+
+    assert_eq!(s.client.get_group_reserve(&circle_id), 10);
+    assert_eq!(s.client.get_group_reserve(&other_circle), 0);
+}
+
+#[test]
+fn distribute_reserve_splits_equally_and_keeps_remainder() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+    let late = s.join(circle_id, 1_010);
+    let second = s.join(circle_id, 0);
+    let third = s.join(circle_id, 0);
+
+    // One late deposit puts a 10 penalty in the reserve
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&late, &circle_id, &1);
+
+    assert_eq!(s.client.distribute_reserve(&s.creator, &circle_id), 3);
+    for member in [&late, &second, &third] {
+        assert_eq!(s.balance(member), 3);
+    }
+    assert_eq!(s.client.get_group_reserve(&circle_id), 1);
+}
+
+#[test]
+fn distribution_leaves_prepaid_credit_refundable() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        max_members: 3,
+        ..s.config()
+    });
+    let prepaid = s.join(circle_id, 10);
+    let late = s.join(circle_id, 1_010);
+    let third = s.join(circle_id, 0);
+    s.client.prepay_penalty(&prepaid, &circle_id, &10);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&late, &circle_id, &1);
+    assert_eq!(s.client.distribute_reserve(&s.creator, &circle_id), 3);
+
+    s.client.propose_cancel(&prepaid, &circle_id);
+    s.client.vote_cancel(&prepaid, &circle_id, &true);
+    s.client.vote_cancel(&third, &circle_id, &true);
+
+    assert_eq!(s.client.claim_refund(&prepaid, &circle_id), 10);
+    assert_eq!(s.balance(&prepaid), 13);
+}
+
+#[test]
+fn ended_circle_distributes_but_frozen_circle_cannot() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+    let late = s.join(circle_id, 1_010);
+    let second = s.join(circle_id, 0);
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&late, &circle_id, &1);
+
+    s.client.freeze_circle(&s.creator, &circle_id);
+    assert_eq!(
+        s.client.try_distribute_reserve(&s.creator, &circle_id),
+        Err(Ok(Error::CircleFrozen.into()))
+    );
+    s.client.unfreeze_circle(&s.creator, &circle_id);
+
+    s.client.propose_cancel(&late, &circle_id);
+    s.client.vote_cancel(&late, &circle_id, &true);
+    s.client.vote_cancel(&second, &circle_id, &true);
+
+    // Late penalties stay shareable after cancellation
+    assert_eq!(s.client.distribute_reserve(&s.creator, &circle_id), 5);
+    assert_eq!(s.balance(&second), 5);
+    assert_eq!(s.client.get_group_reserve(&circle_id), 0);
+}
+
+#[test]
+fn reserve_covers_a_defaulting_member() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        bond_amount: 1_000,
+        ..s.config()
+    });
+    let payer = s.join(circle_id, 10_000);
+    let defaulter = s.join(circle_id, 0);
+
+    // A late payment and a slashed bond fund the reserve
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&payer, &circle_id, &1);
+    s.client.slash_bond(&s.admin, &circle_id);
+    assert_eq!(s.client.get_group_reserve(&circle_id), 1_010);
+
+    s.client
+        .cover_from_reserve(&s.creator, &circle_id, &defaulter);
+
+    let covered = s.client.get_member(&circle_id, &defaulter);
+    assert!(covered.has_contributed);
+    assert_eq!(covered.contribution_count, 1);
+    assert_eq!(covered.last_contribution_time, env.ledger().timestamp());
+    assert_eq!(s.client.get_group_reserve(&circle_id), 10);
+}
+
+#[test]
+fn settled_member_cannot_be_covered_again() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        bond_amount: 2_000,
+        ..s.config()
+    });
+    let defaulter = s.join(circle_id, 0);
+    let payer = s.join(circle_id, 1_000);
+    s.client.slash_bond(&s.admin, &circle_id);
+
+    env.ledger().with_mut(|li| li.timestamp += 10);
+    s.client
+        .cover_from_reserve(&s.creator, &circle_id, &defaulter);
+    s.client.deposit(&payer, &circle_id, &1);
+    for member in [&defaulter, &payer] {
+        assert_eq!(
+            s.client
+                .try_cover_from_reserve(&s.creator, &circle_id, member),
+            Err(Ok(Error::AlreadyContributed.into()))
+        );
+    }
+    assert_eq!(
+        s.client
+            .get_member(&circle_id, &defaulter)
+            .contribution_count,
+        1
+    );
+    assert_eq!(s.client.get_group_reserve(&circle_id), 1_000);
+}
+
+#[test]
+fn covered_contribution_returns_to_reserve_when_circle_ends() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        bond_amount: 1_000,
+        ..s.config()
+    });
+    let voter = s.join(circle_id, 0);
+    let defaulter = s.join(circle_id, 0);
+    s.client.slash_bond(&s.admin, &circle_id);
+    s.client
+        .cover_from_reserve(&s.creator, &circle_id, &defaulter);
+    assert_eq!(s.client.get_group_reserve(&circle_id), 0);
+
+    s.client.propose_cancel(&voter, &circle_id);
+    s.client.vote_cancel(&voter, &circle_id, &true);
+    s.client.vote_cancel(&defaulter, &circle_id, &true);
+
+    assert_eq!(s.client.get_group_reserve(&circle_id), 1_000);
+}
+
+#[test]
+fn coverage_tops_up_installments_only() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        bond_amount: 1_000,
+        ..s.config()
+    });
+    let partial = s.join(circle_id, 400);
+    s.fill(circle_id, 1);
+    s.client.deposit_partial(&partial, &circle_id, &400);
+    s.client.slash_bond(&s.admin, &circle_id);
+
+    s.client
+        .cover_from_reserve(&s.creator, &circle_id, &partial);
+
+    assert_eq!(s.client.get_group_reserve(&circle_id), 400);
+    assert_eq!(s.client.get_member(&circle_id, &partial).paid_this_cycle, 0);
+}
+
+#[test]
+fn frozen_circle_cannot_cover() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.create_circle(&CircleConfig {
+        bond_amount: 1_000,
+        ..s.config()
+    });
+    let defaulter = s.join(circle_id, 0);
+    s.fill(circle_id, 1);
+    s.client.slash_bond(&s.admin, &circle_id);
+
+    s.client.freeze_circle(&s.creator, &circle_id);
+    assert_eq!(
+        s.client
+            .try_cover_from_reserve(&s.creator, &circle_id, &defaulter),
+        Err(Ok(Error::CircleFrozen.into()))
+    );
+}
+
+#[test]
+fn coverage_rejected_when_reserve_is_short() {
+    let env = Env::default();
+    let s = Suite::new(&env);
+    let circle_id = s.circle();
+    let payer = s.join(circle_id, 10_000);
+    let defaulter = s.join(circle_id, 0);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * CYCLE);
+    s.client.deposit(&payer, &circle_id, &1);
+
+    assert_eq!(
+        s.client
+            .try_cover_from_reserve(&s.creator, &circle_id, &defaulter),
+        Err(Ok(Error::InsufficientReserve.into()))
+    );
+    assert!(!s.client.get_member(&circle_id, &defaulter).has_contributed);
+}